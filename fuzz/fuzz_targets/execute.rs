@@ -0,0 +1,9 @@
+//! Fuzz the interpreter from raw bytes through the deterministic harness
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = tinyevm::fuzz::fuzz_execute(data);
+});
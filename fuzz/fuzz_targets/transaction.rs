@@ -0,0 +1,10 @@
+//! Fuzz the interpreter with structured transactions
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tinyevm::fuzz::FuzzTransaction;
+
+fuzz_target!(|tx: FuzzTransaction| {
+    let _ = tx.execute();
+});
@@ -0,0 +1,199 @@
+//! Fuzzing entry points for TinyEVM
+//!
+//! This module exposes fuzz-friendly input types and a single deterministic
+//! harness, so the interpreter can be driven by cargo-fuzz (see `fuzz/`) or
+//! any other coverage-guided fuzzer that hands out raw bytes.
+//!
+//! Only available with the `fuzzing` feature enabled.
+
+use crate::evm::context::ExecutionContext;
+use crate::evm::opcodes::Opcode;
+use crate::evm::EVM;
+use crate::types::*;
+use arbitrary::{Arbitrary, Unstructured};
+use std::sync::OnceLock;
+
+/// Maximum number of instructions in generated bytecode
+pub const MAX_FUZZ_INSTRUCTIONS: usize = 256;
+
+/// Maximum gas limit of a generated transaction
+pub const MAX_FUZZ_GAS: Gas = 1_000_000;
+
+/// Maximum length of generated call data
+pub const MAX_FUZZ_DATA: usize = 256;
+
+/// Known opcode bytes, computed once
+fn known_opcodes() -> &'static [u8] {
+    static KNOWN: OnceLock<Vec<u8>> = OnceLock::new();
+    KNOWN.get_or_init(|| {
+        (0..=u8::MAX)
+            .filter(|byte| Opcode::from_byte(*byte).is_some())
+            .collect()
+    })
+}
+
+/// Single instruction of generated bytecode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// Opcode from the known opcode table, with its full PUSH immediate
+    Opcode { byte: u8, immediate: Bytes },
+
+    /// Raw (possibly invalid) byte, emitted without immediate data
+    Raw(u8),
+}
+
+impl<'a> Arbitrary<'a> for Instruction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // One in eight instructions is a raw byte. A raw PUSH is left without
+        // its immediate, so code can end inside PUSH data.
+        if u.ratio(1u8, 8u8)? {
+            return Ok(Instruction::Raw(u8::arbitrary(u)?));
+        }
+
+        let byte = *u.choose(known_opcodes())?;
+        let mut immediate = Vec::new();
+        if let Some(opcode) = Opcode::from_byte(byte) {
+            if opcode.is_push() {
+                immediate = vec![0u8; opcode.immediate_bytes()];
+                u.fill_buffer(&mut immediate)?;
+            }
+        }
+
+        Ok(Instruction::Opcode { byte, immediate })
+    }
+}
+
+impl Instruction {
+    /// Append the encoded instruction to `code`
+    pub fn encode(&self, code: &mut Bytes) {
+        match self {
+            Instruction::Opcode { byte, immediate } => {
+                code.push(*byte);
+                code.extend_from_slice(immediate);
+            }
+            Instruction::Raw(byte) => code.push(*byte),
+        }
+    }
+}
+
+/// Contract bytecode built from fuzzer input
+///
+/// Most instructions are drawn from the known opcode table (with PUSH
+/// immediates appended), so generated programs get past the decoder instead
+/// of failing on the first byte. Raw bytes are still mixed in now and then
+/// to keep invalid opcodes and truncated PUSH data covered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bytecode(pub Bytes);
+
+impl Bytecode {
+    /// Encode a sequence of instructions
+    pub fn from_instructions(instructions: &[Instruction]) -> Self {
+        let mut code = Vec::new();
+        for instruction in instructions {
+            instruction.encode(&mut code);
+        }
+        Bytecode(code)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Bytecode {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut code = Vec::new();
+        let instructions = u.int_in_range(0..=MAX_FUZZ_INSTRUCTIONS)?;
+        for _ in 0..instructions {
+            Instruction::arbitrary(u)?.encode(&mut code);
+        }
+
+        Ok(Bytecode(code))
+    }
+}
+
+/// Transaction built from fuzzer input
+#[derive(Debug, Clone)]
+pub struct FuzzTransaction {
+    /// Sender of the transaction (also used as origin)
+    pub caller: Address,
+
+    /// Address of the contract being executed
+    pub address: Address,
+
+    /// ETH value sent with the call
+    pub value: Wei,
+
+    /// Call data
+    pub data: Bytes,
+
+    /// Code of the contract being executed
+    pub code: Bytecode,
+
+    /// Gas limit for execution
+    pub gas_limit: Gas,
+}
+
+impl<'a> Arbitrary<'a> for FuzzTransaction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Fixed-size fields come first so they never read from exhausted
+        // input; the code is drawn last and takes whatever is left.
+        let gas_limit = u.int_in_range(0..=MAX_FUZZ_GAS)?;
+        let caller = Address::from(<[u8; 20]>::arbitrary(u)?);
+        let address = Address::from(<[u8; 20]>::arbitrary(u)?);
+        let value = Wei::from_big_endian(&<[u8; 32]>::arbitrary(u)?);
+
+        let mut data = vec![0u8; u.int_in_range(0..=MAX_FUZZ_DATA)?];
+        u.fill_buffer(&mut data)?;
+
+        Ok(Self {
+            caller,
+            address,
+            value,
+            data,
+            code: Bytecode::arbitrary(u)?,
+            gas_limit,
+        })
+    }
+}
+
+impl FuzzTransaction {
+    /// Build the execution context for this transaction
+    pub fn context(&self) -> ExecutionContext {
+        ExecutionContext::new(
+            self.address,
+            self.caller,
+            self.caller,
+            self.value,
+            self.data.clone(),
+            self.code.0.clone(),
+            BlockContext::default(),
+            Wei::zero(),
+        )
+    }
+
+    /// Execute this transaction on a fresh EVM
+    ///
+    /// # Panics
+    /// Panics if execution breaks an interpreter invariant (e.g. more gas
+    /// used than the gas limit), so fuzzers report it as a crash.
+    pub fn execute(&self) -> Result<ExecutionResult> {
+        let mut evm = EVM::new(self.context(), self.gas_limit);
+        let result = evm.execute();
+
+        assert!(evm.gas <= self.gas_limit, "gas remaining exceeds gas limit");
+        if let Ok(result) = &result {
+            assert!(result.gas_used <= self.gas_limit, "gas used exceeds gas limit");
+        }
+
+        result
+    }
+}
+
+/// Deterministically execute a transaction derived from raw fuzzer bytes
+///
+/// The same `seed_bytes` always produce the same transaction and the same
+/// outcome. Execution errors (out of gas, invalid opcodes, ...) are returned
+/// as usual; only broken invariants panic.
+pub fn fuzz_execute(seed_bytes: &[u8]) -> Result<ExecutionResult> {
+    let u = Unstructured::new(seed_bytes);
+    let tx = FuzzTransaction::arbitrary_take_rest(u)
+        .map_err(|e| Error::InvalidTransaction(format!("fuzz input: {}", e)))?;
+    tx.execute()
+}
@@ -7,4 +7,7 @@ pub mod evm;
 pub mod state;
 pub mod gas;
 
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+
 pub use types::*;
\ No newline at end of file
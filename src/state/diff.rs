@@ -0,0 +1,134 @@
+//! State Diffs
+//!
+//! This module compares two versions of the world state and lists every
+//! account field and storage slot that changed between them. Diffs are
+//! taken against a `StateSnapshot`, typically one created right before
+//! an execution.
+
+use crate::state::{Account, State, StateSnapshot};
+use crate::evm::storage::Storage;
+use crate::types::*;
+use std::collections::{BTreeSet, HashMap};
+
+/// Change of a single account's fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountChange {
+    /// Account address
+    pub address: Address,
+
+    /// Balance before and after (if changed)
+    pub balance: Option<(Wei, Wei)>,
+
+    /// Nonce before and after (if changed)
+    pub nonce: Option<(Nonce, Nonce)>,
+
+    /// Whether the contract code changed
+    pub code_changed: bool,
+}
+
+/// Change of a single storage slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    /// Contract address owning the slot
+    pub address: Address,
+
+    /// Storage slot
+    pub slot: Word,
+
+    /// Value before the change
+    pub before: Word,
+
+    /// Value after the change
+    pub after: Word,
+}
+
+/// Differences between two versions of the world state
+///
+/// Changes are sorted by address (and slot), so the same pair of states
+/// always produces the same diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Changed accounts
+    pub accounts: Vec<AccountChange>,
+
+    /// Changed storage slots
+    pub storage: Vec<StorageChange>,
+}
+
+impl StateDiff {
+    /// Compute the diff from one snapshot to another
+    pub fn between(before: &StateSnapshot, after: &StateSnapshot) -> Self {
+        Self::compute(&before.accounts, &before.storage, &after.accounts, &after.storage)
+    }
+
+    /// Check if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.storage.is_empty()
+    }
+
+    /// All addresses touched by this diff
+    pub fn addresses(&self) -> BTreeSet<Address> {
+        self.accounts
+            .iter()
+            .map(|change| change.address)
+            .chain(self.storage.iter().map(|change| change.address))
+            .collect()
+    }
+
+    fn compute(
+        before_accounts: &HashMap<Address, Account>,
+        before_storage: &HashMap<Address, Storage>,
+        after_accounts: &HashMap<Address, Account>,
+        after_storage: &HashMap<Address, Storage>,
+    ) -> Self {
+        let mut diff = StateDiff::default();
+
+        // Accounts (missing accounts compare as empty EOAs)
+        let addresses: BTreeSet<Address> = before_accounts.keys().chain(after_accounts.keys()).copied().collect();
+        let empty = Account::new_eoa();
+        for address in addresses {
+            let before = before_accounts.get(&address).unwrap_or(&empty);
+            let after = after_accounts.get(&address).unwrap_or(&empty);
+
+            let change = AccountChange {
+                address,
+                balance: (before.balance != after.balance).then_some((before.balance, after.balance)),
+                nonce: (before.nonce != after.nonce).then_some((before.nonce, after.nonce)),
+                code_changed: before.code_hash != after.code_hash,
+            };
+            if change.balance.is_some() || change.nonce.is_some() || change.code_changed {
+                diff.accounts.push(change);
+            }
+        }
+
+        // Storage (missing slots compare as zero)
+        let addresses: BTreeSet<Address> = before_storage.keys().chain(after_storage.keys()).copied().collect();
+        for address in addresses {
+            let load = |storage: &HashMap<Address, Storage>, slot: &Word| {
+                storage.get(&address).map(|s| s.load(slot)).unwrap_or(Word::zero())
+            };
+            let slots: BTreeSet<Word> = [before_storage.get(&address), after_storage.get(&address)]
+                .into_iter()
+                .flatten()
+                .flat_map(|storage| storage.data().keys().copied())
+                .collect();
+
+            for slot in slots {
+                let before = load(before_storage, &slot);
+                let after = load(after_storage, &slot);
+                if before != after {
+                    diff.storage.push(StorageChange { address, slot, before, after });
+                }
+            }
+        }
+
+        diff
+    }
+}
+
+impl State {
+    /// Compute the changes made since the given snapshot was taken
+    pub fn diff_since(&self, snapshot: &StateSnapshot) -> StateDiff {
+        StateDiff::compute(&snapshot.accounts, &snapshot.storage, &self.accounts, &self.storage)
+    }
+}
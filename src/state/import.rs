@@ -0,0 +1,300 @@
+//! State Snapshot Import
+//!
+//! This module loads exported world state into a `State` so that real
+//! contract ecosystems can be simulated locally. It reads the line-delimited
+//! format produced by `geth dump --iterative`, where every line is a single
+//! JSON account record (preceded by a `{"root": ...}` header line).
+//!
+//! Records are parsed one at a time from the underlying reader, so the dump
+//! never has to be held in memory as a whole.
+
+use crate::state::{Account, State};
+use crate::types::*;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// Default number of accounts between two progress reports
+pub const DEFAULT_REPORT_INTERVAL: u64 = 10_000;
+
+/// Keccak256 of empty code, as geth reports it for accounts without code
+const EMPTY_CODE_HASH: [u8; 32] = [
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+];
+
+/// Root of an empty storage trie
+const EMPTY_STORAGE_ROOT: [u8; 32] = [
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+];
+
+/// Progress of a running (or finished) snapshot import
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportProgress {
+    /// Accounts written into the state
+    pub accounts: u64,
+
+    /// Storage slots written into the state
+    pub storage_slots: u64,
+
+    /// Records skipped because the address preimage is missing
+    pub skipped: u64,
+
+    /// Accounts with a code hash but no code (dumps made with `--nocode`)
+    pub missing_code: u64,
+
+    /// Accounts with a storage root but no storage (dumps made with `--nostorage`)
+    pub missing_storage: u64,
+
+    /// Bytes consumed from the input so far
+    pub bytes_read: u64,
+
+    /// State root announced in the dump header (if any)
+    pub state_root: Option<Hash>,
+}
+
+/// A single record of a `geth dump --iterative` export
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DumpRecord {
+    #[serde(default)]
+    address: Option<String>,
+
+    #[serde(default)]
+    balance: Option<String>,
+
+    #[serde(default)]
+    nonce: Nonce,
+
+    #[serde(default)]
+    root: Option<String>,
+
+    #[serde(default)]
+    code_hash: Option<String>,
+
+    #[serde(default)]
+    code: Option<String>,
+
+    #[serde(default)]
+    storage: Option<HashMap<String, String>>,
+
+    /// Only present in non-streaming dumps, which are rejected
+    #[serde(default)]
+    accounts: Option<IgnoredAny>,
+}
+
+impl DumpRecord {
+    /// Check if this is the `{"root": ...}` header line
+    fn is_header(&self) -> bool {
+        self.root.is_some()
+            && self.address.is_none()
+            && self.balance.is_none()
+            && self.code_hash.is_none()
+            && self.code.is_none()
+            && self.storage.is_none()
+    }
+}
+
+/// Streaming importer for exported state snapshots
+#[derive(Debug, Clone)]
+pub struct SnapshotImporter {
+    /// Number of accounts between two progress reports
+    report_interval: u64,
+}
+
+impl SnapshotImporter {
+    /// Create a new importer with the default report interval
+    pub fn new() -> Self {
+        Self {
+            report_interval: DEFAULT_REPORT_INTERVAL,
+        }
+    }
+
+    /// Set how many accounts are imported between two progress reports
+    pub fn with_report_interval(mut self, report_interval: u64) -> Self {
+        self.report_interval = report_interval.max(1);
+        self
+    }
+
+    /// Import a dump from a reader into the given state
+    ///
+    /// # Arguments
+    /// * `reader` - Source of the line-delimited dump
+    /// * `state` - State the accounts are written into
+    /// * `on_progress` - Called every `report_interval` accounts and once at the end
+    ///
+    /// # Returns
+    /// The final import totals
+    pub fn import<R, F>(&self, reader: R, state: &mut State, mut on_progress: F) -> Result<ImportProgress>
+    where
+        R: Read,
+        F: FnMut(&ImportProgress),
+    {
+        let mut progress = ImportProgress::default();
+        let mut records = serde_json::Deserializer::from_reader(reader).into_iter::<DumpRecord>();
+        let mut first = true;
+
+        while let Some(record) = records.next() {
+            let record = record?;
+            progress.bytes_read = records.byte_offset() as u64;
+            let is_first = std::mem::replace(&mut first, false);
+
+            if record.accounts.is_some() {
+                return Err(Error::InvalidSnapshot(
+                    "non-streaming dump (use `geth dump --iterative`)".to_string(),
+                ));
+            }
+
+            // The header line only carries the state root
+            if record.is_header() {
+                if !is_first {
+                    return Err(Error::InvalidSnapshot("header record after the first line".to_string()));
+                }
+                if let Some(root) = &record.root {
+                    progress.state_root = Some(parse_hash(root)?);
+                }
+                continue;
+            }
+
+            if record.address.is_none() && record.balance.is_none() {
+                return Err(Error::InvalidSnapshot("record has neither address nor balance".to_string()));
+            }
+
+            // Accounts without an address preimage cannot be placed in the state
+            let address = match &record.address {
+                Some(address) => parse_address(address)?,
+                None => {
+                    progress.skipped += 1;
+                    continue;
+                }
+            };
+
+            progress.storage_slots += apply_record(state, address, &record)?;
+            progress.accounts += 1;
+
+            // Contents left out of the dump are imported as empty
+            if record.code.is_none() && !is_empty_hash(&record.code_hash, &EMPTY_CODE_HASH)? {
+                progress.missing_code += 1;
+            }
+            if record.storage.is_none() && !is_empty_hash(&record.root, &EMPTY_STORAGE_ROOT)? {
+                progress.missing_storage += 1;
+            }
+
+            if progress.accounts % self.report_interval == 0 {
+                on_progress(&progress);
+            }
+        }
+
+        on_progress(&progress);
+        Ok(progress)
+    }
+
+    /// Import a dump file into the given state
+    pub fn import_file<P, F>(&self, path: P, state: &mut State, on_progress: F) -> Result<ImportProgress>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ImportProgress),
+    {
+        let file = File::open(path)?;
+        self.import(BufReader::new(file), state, on_progress)
+    }
+}
+
+impl Default for SnapshotImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Import a `geth dump --iterative` export without progress reporting
+pub fn import_geth_dump<R: Read>(reader: R, state: &mut State) -> Result<ImportProgress> {
+    SnapshotImporter::new().import(reader, state, |_| {})
+}
+
+/// Write a single dump record into the state, returning the number of storage slots set
+///
+/// # Explanation
+/// The record replaces the account entirely: code and storage already in
+/// the state for this address are dropped, not merged with the dump.
+fn apply_record(state: &mut State, address: Address, record: &DumpRecord) -> Result<u64> {
+    let mut account = Account::new_eoa();
+    account.balance = match &record.balance {
+        Some(balance) => parse_balance(balance)?,
+        None => Wei::zero(),
+    };
+    account.nonce = record.nonce;
+    state.set_account(address, account);
+    state.remove_storage(&address);
+
+    if let Some(code) = &record.code {
+        let code = hex::decode(strip_hex_prefix(code))?;
+        state.set_code(address, code);
+    }
+
+    let mut slots = 0;
+    for (key, value) in record.storage.iter().flatten() {
+        let value = parse_word(value)?;
+        if !value.is_zero() {
+            state.store_storage(&address, parse_word(key)?, value);
+            slots += 1;
+        }
+    }
+
+    Ok(slots)
+}
+
+/// Strip an optional `0x` prefix from a hex string
+fn strip_hex_prefix(value: &str) -> &str {
+    value.strip_prefix("0x").unwrap_or(value)
+}
+
+/// Parse a balance, which geth writes in decimal (hex is accepted as well)
+fn parse_balance(value: &str) -> Result<Wei> {
+    if value.starts_with("0x") {
+        return parse_word(value);
+    }
+    Wei::from_dec_str(value)
+        .map_err(|e| Error::InvalidSnapshot(format!("invalid balance {}: {}", value, e)))
+}
+
+/// Parse a hex encoded 256-bit word (storage keys and values)
+fn parse_word(value: &str) -> Result<Word> {
+    let digits = strip_hex_prefix(value);
+    if digits.is_empty() {
+        return Ok(Word::zero());
+    }
+    Word::from_str_radix(digits, 16)
+        .map_err(|e| Error::InvalidSnapshot(format!("invalid word {}: {}", value, e)))
+}
+
+/// Check if an optional hash is absent, zero or equal to the given empty hash
+fn is_empty_hash(value: &Option<String>, empty: &[u8; 32]) -> Result<bool> {
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(true),
+    };
+    let bytes = hex::decode(strip_hex_prefix(value))?;
+    Ok(bytes.iter().all(|byte| *byte == 0) || bytes.as_slice() == empty)
+}
+
+/// Parse a hex encoded 20-byte address
+fn parse_address(value: &str) -> Result<Address> {
+    let bytes = hex::decode(strip_hex_prefix(value))?;
+    if bytes.len() != 20 {
+        return Err(Error::InvalidSnapshot(format!("invalid address {}", value)));
+    }
+    Ok(Address::from_slice(&bytes))
+}
+
+/// Parse a hex encoded 32-byte hash
+fn parse_hash(value: &str) -> Result<Hash> {
+    let bytes = hex::decode(strip_hex_prefix(value))?;
+    if bytes.len() != 32 {
+        return Err(Error::InvalidSnapshot(format!("invalid hash {}", value)));
+    }
+    Ok(Hash::from_slice(&bytes))
+}
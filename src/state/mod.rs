@@ -4,10 +4,28 @@
 //! contract code, and storage. It provides the foundation for all
 //! stateful operations in the EVM.
 
+pub mod diff;
+pub mod import;
+pub mod report;
+
 use crate::types::*;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 use std::collections::HashMap;
 
+/// Hash of contract code (Keccak256), or zero for empty code
+///
+/// # Explanation
+/// A zero code hash marks an externally owned account, so empty code keeps
+/// the zero hash instead of Keccak256 of the empty string.
+pub fn code_hash(code: &[u8]) -> Hash {
+    if code.is_empty() {
+        Hash::zero()
+    } else {
+        Hash::from_slice(&Keccak256::digest(code))
+    }
+}
+
 /// Account information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
@@ -37,21 +55,10 @@ impl Account {
     
     /// Create a new contract account
     pub fn new_contract(code: &[u8]) -> Self {
-        let code_hash = if code.is_empty() {
-            Hash::zero()
-        } else {
-            // In a real implementation, this would be the Keccak256 hash
-            // For now, we'll pad the code to 32 bytes and use it as a simple hash
-            let mut padded_code = [0u8; 32];
-            let copy_len = code.len().min(32);
-            padded_code[..copy_len].copy_from_slice(&code[..copy_len]);
-            Hash::from(padded_code)
-        };
-        
         Self {
             balance: Wei::zero(),
             nonce: 0,
-            code_hash,
+            code_hash: code_hash(code),
             storage_root: Hash::zero(),
         }
     }
@@ -166,16 +173,7 @@ impl State {
     
     /// Set contract code
     pub fn set_code(&mut self, address: Address, code: Bytes) {
-        let code_hash = if code.is_empty() {
-            Hash::zero()
-        } else {
-            // In a real implementation, this would be the Keccak256 hash
-            // For now, we'll pad the code to 32 bytes and use it as a simple hash
-            let mut padded_code = [0u8; 32];
-            let copy_len = code.len().min(32);
-            padded_code[..copy_len].copy_from_slice(&code[..copy_len]);
-            Hash::from(padded_code)
-        };
+        let code_hash = code_hash(&code);
         
         // Update account
         let account = self.get_account_mut(&address);
@@ -206,6 +204,11 @@ impl State {
         storage.store(key, value);
     }
     
+    /// Remove all storage of an account
+    pub fn remove_storage(&mut self, address: &Address) {
+        self.storage.remove(address);
+    }
+    
     /// Create a snapshot of the current state
     pub fn snapshot(&self) -> StateSnapshot {
         StateSnapshot {
@@ -334,6 +337,22 @@ mod tests {
         assert_eq!(state.load_storage(&address, &key), value);
     }
     
+    #[test]
+    fn test_remove_storage() {
+        let mut state = State::new();
+        let address = Address::from([1u8; 20]);
+        
+        state.store_storage(&address, Word::from(1), Word::from(100));
+        state.remove_storage(&address);
+        assert_eq!(state.load_storage(&address, &Word::from(1)), Word::zero());
+        assert!(!state.storage.contains_key(&address));
+        
+        // Importing an EOA does not create an empty storage entry
+        let dump = r#"{"balance":"1","nonce":0,"address":"0x0202020202020202020202020202020202020202"}"#;
+        import::import_geth_dump(dump.as_bytes(), &mut state).unwrap();
+        assert!(state.storage.is_empty());
+    }
+    
     #[test]
     fn test_snapshot_revert() {
         let mut state = State::new();
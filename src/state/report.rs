@@ -0,0 +1,414 @@
+//! Human-readable State Change Reports
+//!
+//! This module turns a `StateDiff` into lines like
+//! `USDC.balanceOf(0xabcd…1234) 100 → 250`. Contracts are labeled with a
+//! name and a storage layout (hand-written or taken from solc's
+//! `storageLayout` output). Each variable's display name is the name users
+//! should see, for example the ABI getter `balanceOf` rather than the
+//! storage label `_balances`.
+//!
+//! Mapping keys cannot be recovered from hashed slots. Instead the reporter
+//! hashes a set of candidate keys and matches them against the changed
+//! slots. Candidates are every address touched by the diff plus any keys
+//! registered with `with_known_address` or `with_known_key`. Keys are
+//! printed as addresses or integers depending on where they came from.
+
+use crate::state::diff::StateDiff;
+use crate::types::*;
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+use std::fmt;
+
+/// How a storage variable maps onto slots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariableKind {
+    /// Single value stored directly at the variable's slot
+    Value,
+
+    /// Mapping whose entries live at `keccak256(key . slot)`
+    Mapping,
+}
+
+/// Named storage variable of a contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageVariable {
+    /// Display name (e.g. `totalSupply` or `balanceOf`)
+    pub name: String,
+
+    /// Base slot of the variable
+    pub slot: Word,
+
+    /// Byte offset inside the slot, counted from the low-order end
+    pub offset: usize,
+
+    /// Size in bytes (32 for a full slot)
+    pub size: usize,
+
+    /// Slot layout of the variable
+    pub kind: VariableKind,
+}
+
+impl StorageVariable {
+    /// Extract this variable's bytes from a full slot value
+    fn extract(&self, value: &Word) -> Word {
+        if self.offset == 0 && self.size >= 32 {
+            return *value;
+        }
+        let shifted = *value >> (self.offset * 8);
+        if self.size >= 32 {
+            shifted
+        } else {
+            shifted & ((Word::one() << (self.size * 8)) - Word::one())
+        }
+    }
+}
+
+/// Storage layout of a contract
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageLayout {
+    /// Known storage variables
+    pub variables: Vec<StorageVariable>,
+}
+
+/// solc `storageLayout` output (only the fields we need)
+#[derive(Debug, Deserialize)]
+struct SolcLayout {
+    storage: Vec<SolcVariable>,
+    #[serde(default)]
+    types: HashMap<String, SolcType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolcVariable {
+    label: String,
+    slot: String,
+    #[serde(default)]
+    offset: usize,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolcType {
+    number_of_bytes: String,
+}
+
+impl StorageLayout {
+    /// Create an empty layout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a plain value variable occupying a full slot
+    pub fn with_value(self, name: &str, slot: u64) -> Self {
+        self.with_packed_value(name, slot, 0, 32)
+    }
+
+    /// Add a value variable packed into part of a slot
+    ///
+    /// # Arguments
+    /// * `offset` - Byte offset inside the slot, counted from the low-order end
+    /// * `size` - Size of the variable in bytes
+    pub fn with_packed_value(mut self, name: &str, slot: u64, offset: usize, size: usize) -> Self {
+        self.variables.push(StorageVariable {
+            name: name.to_string(),
+            slot: Word::from(slot),
+            offset,
+            size,
+            kind: VariableKind::Value,
+        });
+        self
+    }
+
+    /// Add a mapping variable
+    pub fn with_mapping(mut self, name: &str, slot: u64) -> Self {
+        self.variables.push(StorageVariable {
+            name: name.to_string(),
+            slot: Word::from(slot),
+            offset: 0,
+            size: 32,
+            kind: VariableKind::Mapping,
+        });
+        self
+    }
+
+    /// Parse the `storageLayout` JSON emitted by solc
+    ///
+    /// # Explanation
+    /// Variables keep their source labels; use `rename` to show ABI getter
+    /// names instead. Packed variables keep their `offset` and the size of
+    /// their type, so each one is reported with only its own bytes.
+    pub fn from_solc_json(json: &str) -> Result<Self> {
+        let layout: SolcLayout = serde_json::from_str(json)?;
+
+        let mut variables = Vec::with_capacity(layout.storage.len());
+        for variable in layout.storage {
+            let slot = Word::from_dec_str(&variable.slot)
+                .map_err(|e| Error::InvalidStorageLayout(format!("invalid slot {}: {}", variable.slot, e)))?;
+            let kind = if variable.ty.starts_with("t_mapping(") {
+                VariableKind::Mapping
+            } else {
+                VariableKind::Value
+            };
+            let size = match layout.types.get(&variable.ty) {
+                Some(ty) => ty.number_of_bytes.parse::<usize>().map_err(|e| {
+                    Error::InvalidStorageLayout(format!("invalid size {}: {}", ty.number_of_bytes, e))
+                })?,
+                None => 32,
+            };
+            if variable.offset >= 32 {
+                return Err(Error::InvalidStorageLayout(format!("invalid offset {}", variable.offset)));
+            }
+            variables.push(StorageVariable {
+                name: variable.label,
+                slot,
+                offset: variable.offset,
+                size,
+                kind,
+            });
+        }
+
+        Ok(Self { variables })
+    }
+
+    /// Change the display name of a variable (e.g. `_balances` to `balanceOf`)
+    pub fn rename(mut self, from: &str, to: &str) -> Self {
+        for variable in self.variables.iter_mut().filter(|v| v.name == from) {
+            variable.name = to.to_string();
+        }
+        self
+    }
+}
+
+/// What happened to the subject of a report line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportChange {
+    /// Value changed from `before` to `after`
+    Value { before: Word, after: Word },
+
+    /// Contract code was deployed, replaced or removed
+    Code,
+}
+
+/// Single line of a state change report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportLine {
+    /// What changed (e.g. `USDC.balanceOf(0xabcd…1234)`)
+    pub subject: String,
+
+    /// How it changed
+    pub change: ReportChange,
+}
+
+impl fmt::Display for ReportLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            ReportChange::Value { before, after } => write!(f, "{} {} → {}", self.subject, before, after),
+            ReportChange::Code => write!(f, "{} changed", self.subject),
+        }
+    }
+}
+
+/// Human-readable report of a state diff
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateReport {
+    /// Report lines, in diff order
+    pub lines: Vec<ReportLine>,
+}
+
+impl fmt::Display for StateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Candidate mapping key, remembering how it should be printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MappingKey {
+    /// Address key, left-padded to a word
+    Address(Address),
+
+    /// Raw word key (token ids, indices, ...)
+    Word(Word),
+}
+
+impl MappingKey {
+    /// Key as hashed into the mapping slot
+    fn word(&self) -> Word {
+        match self {
+            MappingKey::Address(address) => Word::from_big_endian(address.as_bytes()),
+            MappingKey::Word(word) => *word,
+        }
+    }
+}
+
+impl fmt::Display for MappingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingKey::Address(address) => write!(f, "{}", short_address(address)),
+            MappingKey::Word(word) => write!(f, "{}", word),
+        }
+    }
+}
+
+/// Named contract known to the reporter
+#[derive(Debug, Clone)]
+struct LabeledContract {
+    name: String,
+    layout: StorageLayout,
+}
+
+/// Builds human-readable reports from state diffs
+#[derive(Debug, Clone, Default)]
+pub struct StateReporter {
+    /// Labeled contracts by address
+    contracts: HashMap<Address, LabeledContract>,
+
+    /// Extra candidate keys for mapping lookups
+    known_keys: Vec<MappingKey>,
+}
+
+impl StateReporter {
+    /// Create a reporter without any labeled contracts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Label a contract with a name and storage layout
+    pub fn with_contract(mut self, address: Address, name: &str, layout: StorageLayout) -> Self {
+        self.contracts.insert(address, LabeledContract { name: name.to_string(), layout });
+        self
+    }
+
+    /// Add a candidate mapping key (e.g. a token id)
+    pub fn with_known_key(mut self, key: Word) -> Self {
+        self.known_keys.push(MappingKey::Word(key));
+        self
+    }
+
+    /// Add an address as a candidate mapping key
+    pub fn with_known_address(mut self, address: Address) -> Self {
+        self.known_keys.push(MappingKey::Address(address));
+        self
+    }
+
+    /// Build the report for a diff
+    pub fn report(&self, diff: &StateDiff) -> StateReport {
+        let mut keys: Vec<MappingKey> = diff.addresses().into_iter().map(MappingKey::Address).collect();
+        keys.extend(self.known_keys.iter().copied());
+
+        let mut lines = Vec::new();
+
+        for change in &diff.accounts {
+            if let Some((before, after)) = change.balance {
+                lines.push(ReportLine {
+                    subject: format!("{}.balance", self.account_name(&change.address)),
+                    change: ReportChange::Value { before, after },
+                });
+            }
+            if let Some((before, after)) = change.nonce {
+                lines.push(ReportLine {
+                    subject: format!("{}.nonce", self.account_name(&change.address)),
+                    change: ReportChange::Value {
+                        before: Word::from(before),
+                        after: Word::from(after),
+                    },
+                });
+            }
+            if change.code_changed {
+                lines.push(ReportLine {
+                    subject: format!("{}.code", self.account_name(&change.address)),
+                    change: ReportChange::Code,
+                });
+            }
+        }
+
+        for change in &diff.storage {
+            let decoded = match self.contracts.get(&change.address) {
+                Some(contract) => decode_slot(&contract.layout, &change.slot, &keys)
+                    .into_iter()
+                    .map(|(variable, name)| (format!("{}.{}", contract.name, name), variable))
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            // Packed variables only report their own bytes, and only if they changed
+            let mut decoded_any = false;
+            for (subject, variable) in decoded {
+                decoded_any = true;
+                let before = variable.extract(&change.before);
+                let after = variable.extract(&change.after);
+                if before != after {
+                    lines.push(ReportLine {
+                        subject,
+                        change: ReportChange::Value { before, after },
+                    });
+                }
+            }
+
+            if !decoded_any {
+                let owner = self.account_name(&change.address);
+                lines.push(ReportLine {
+                    subject: format!("{}.slot[{:#x}]", owner, change.slot),
+                    change: ReportChange::Value {
+                        before: change.before,
+                        after: change.after,
+                    },
+                });
+            }
+        }
+
+        StateReport { lines }
+    }
+
+    /// Contract name if labeled, short address otherwise
+    fn account_name(&self, address: &Address) -> String {
+        match self.contracts.get(address) {
+            Some(contract) => contract.name.clone(),
+            None => short_address(address),
+        }
+    }
+}
+
+/// Name a slot using the layout and candidate mapping keys
+///
+/// # Returns
+/// Every variable stored in the slot with its display name: all packed
+/// values sharing the slot, or a single mapping entry.
+fn decode_slot<'l>(layout: &'l StorageLayout, slot: &Word, keys: &[MappingKey]) -> Vec<(&'l StorageVariable, String)> {
+    let values: Vec<_> = layout
+        .variables
+        .iter()
+        .filter(|variable| variable.kind == VariableKind::Value && &variable.slot == slot)
+        .map(|variable| (variable, variable.name.clone()))
+        .collect();
+    if !values.is_empty() {
+        return values;
+    }
+
+    for variable in layout.variables.iter().filter(|v| v.kind == VariableKind::Mapping) {
+        if let Some(key) = keys.iter().find(|key| &mapping_slot(&key.word(), &variable.slot) == slot) {
+            return vec![(variable, format!("{}({})", variable.name, key))];
+        }
+    }
+    Vec::new()
+}
+
+/// Storage slot of a mapping entry: `keccak256(key . slot)`
+pub fn mapping_slot(key: &Word, slot: &Word) -> Word {
+    let mut preimage = [0u8; 64];
+    key.to_big_endian(&mut preimage[..32]);
+    slot.to_big_endian(&mut preimage[32..]);
+    Word::from_big_endian(&Keccak256::digest(preimage))
+}
+
+/// Abbreviate an address as `0xabcd…1234`
+fn short_address(address: &Address) -> String {
+    let hex = hex::encode(address.as_bytes());
+    format!("0x{}…{}", &hex[..4], &hex[hex.len() - 4..])
+}
@@ -75,6 +75,12 @@ pub enum Error {
     #[error("Invalid signature: {0}")]
     InvalidSignature(String),
     
+    #[error("Invalid state snapshot: {0}")]
+    InvalidSnapshot(String),
+    
+    #[error("Invalid storage layout: {0}")]
+    InvalidStorageLayout(String),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
@@ -0,0 +1,92 @@
+//! Tests for the fuzzing entry points (run with `--features fuzzing`)
+
+#![cfg(feature = "fuzzing")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use tinyevm::evm::opcodes::Opcode;
+use tinyevm::fuzz::{fuzz_execute, Bytecode, FuzzTransaction, Instruction, MAX_FUZZ_DATA, MAX_FUZZ_GAS};
+use tinyevm::types::*;
+
+fn seed(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 + 7) as u8).collect()
+}
+
+#[test]
+fn test_transaction_runs_instructions() {
+    let tx = FuzzTransaction {
+        caller: Address::from([0x11; 20]),
+        address: Address::from([0x22; 20]),
+        value: Wei::zero(),
+        data: Vec::new(),
+        code: Bytecode(vec![0x60, 0x05, 0x60, 0x03, 0x01]),
+        gas_limit: 10000,
+    };
+
+    // PUSH1 + PUSH1 + ADD
+    let result = tx.execute().unwrap();
+    assert!(result.success);
+    assert_eq!(result.gas_used, 9);
+}
+
+#[test]
+fn test_fuzz_execute_is_deterministic() {
+    for len in [0, 16, 64, 256, 1024] {
+        let seed = seed(len);
+        let first = format!("{:?}", fuzz_execute(&seed));
+        let second = format!("{:?}", fuzz_execute(&seed));
+        assert_eq!(first, second);
+    }
+}
+
+#[test]
+fn test_fuzz_execute_handles_any_input() {
+    // Empty and short inputs must not panic
+    let _ = fuzz_execute(&[]);
+    let _ = fuzz_execute(&[0xff]);
+    for len in [16, 64, 256, 1024] {
+        let _ = fuzz_execute(&seed(len));
+    }
+}
+
+#[test]
+fn test_arbitrary_transaction_bounds() {
+    for len in [0, 16, 256, 1024] {
+        let seed = seed(len);
+        let tx = FuzzTransaction::arbitrary(&mut Unstructured::new(&seed)).unwrap();
+        assert!(tx.gas_limit <= MAX_FUZZ_GAS);
+        assert!(tx.data.len() <= MAX_FUZZ_DATA);
+    }
+
+    // Gas limit is drawn before the variable-size fields
+    let tx = FuzzTransaction::arbitrary(&mut Unstructured::new(&seed(4096))).unwrap();
+    assert!(tx.gas_limit > 0);
+}
+
+#[test]
+fn test_arbitrary_instruction_push_immediates() {
+    for variant in 0..500usize {
+        let seed: Vec<u8> = (0..512).map(|i| (i * 31 + variant * 131 + 7) as u8).collect();
+        let mut u = Unstructured::new(&seed);
+
+        let mut instructions = Vec::new();
+        while !u.is_empty() {
+            instructions.push(Instruction::arbitrary(&mut u).unwrap());
+        }
+
+        // PUSHes from the opcode table carry their full immediate; raw bytes carry none
+        let mut expected = Vec::new();
+        for instruction in &instructions {
+            match instruction {
+                Instruction::Opcode { byte, immediate } => {
+                    let opcode = Opcode::from_byte(*byte).unwrap();
+                    let width = if opcode.is_push() { opcode.immediate_bytes() } else { 0 };
+                    assert_eq!(immediate.len(), width);
+                    expected.push(*byte);
+                    expected.extend_from_slice(immediate);
+                }
+                Instruction::Raw(byte) => expected.push(*byte),
+            }
+        }
+        assert_eq!(Bytecode::from_instructions(&instructions), Bytecode(expected));
+    }
+}
@@ -0,0 +1,135 @@
+//! Unit tests for state snapshot import
+
+use tinyevm::state::import::{import_geth_dump, SnapshotImporter};
+use tinyevm::state::State;
+use tinyevm::types::*;
+
+const DUMP: &str = r#"{"root":"0x0101010101010101010101010101010101010101010101010101010101010101"}
+{"balance":"1000","nonce":3,"root":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470","address":"0x0000000000000000000000000000000000000001","key":"0x00"}
+{"balance":"0","nonce":1,"root":"0x00","codeHash":"0x00","code":"0x6001600201","storage":{"0x01":"0x2a","0x02":"0x00"},"address":"0x0000000000000000000000000000000000000002","key":"0x01"}
+{"balance":"5","nonce":0,"root":"0x00","codeHash":"0x00","key":"0x02"}
+"#;
+
+#[test]
+fn test_import_geth_dump() {
+    let mut state = State::new();
+    let progress = import_geth_dump(DUMP.as_bytes(), &mut state).unwrap();
+
+    let eoa = Address::from_low_u64_be(1);
+    let contract = Address::from_low_u64_be(2);
+
+    // Account fields
+    assert_eq!(state.get_balance(&eoa), Wei::from(1000));
+    assert_eq!(state.get_nonce(&eoa), 3);
+    assert!(state.get_code(&eoa).is_none());
+
+    // Contract code and storage (zero values are not stored)
+    assert_eq!(state.get_code(&contract), Some(&vec![0x60, 0x01, 0x60, 0x02, 0x01]));
+    assert_eq!(state.load_storage(&contract, &Word::from(1)), Word::from(42));
+    assert_eq!(state.load_storage(&contract, &Word::from(2)), Word::zero());
+
+    // Totals
+    assert_eq!(progress.accounts, 2);
+    assert_eq!(progress.storage_slots, 1);
+    assert_eq!(progress.skipped, 1);
+    assert_eq!(progress.missing_code, 0);
+    assert_eq!(progress.missing_storage, 0);
+    assert_eq!(progress.bytes_read, DUMP.trim_end().len() as u64);
+    assert_eq!(progress.state_root, Some(Hash::from([1u8; 32])));
+}
+
+#[test]
+fn test_import_progress_reports() {
+    let mut state = State::new();
+    let mut reports = Vec::new();
+
+    SnapshotImporter::new()
+        .with_report_interval(1)
+        .import(DUMP.as_bytes(), &mut state, |progress| reports.push(progress.accounts))
+        .unwrap();
+
+    // One report per account plus the final one
+    assert_eq!(reports, vec![1, 2, 2]);
+}
+
+#[test]
+fn test_import_invalid_record() {
+    let mut state = State::new();
+
+    let bad_address = r#"{"balance":"1","nonce":0,"address":"0x1234"}"#;
+    assert!(import_geth_dump(bad_address.as_bytes(), &mut state).is_err());
+
+    let bad_balance = r#"{"balance":"abc","nonce":0,"address":"0x0000000000000000000000000000000000000001"}"#;
+    assert!(import_geth_dump(bad_balance.as_bytes(), &mut state).is_err());
+
+    let bad_json = r#"{"balance":"1","#;
+    assert!(import_geth_dump(bad_json.as_bytes(), &mut state).is_err());
+}
+
+#[test]
+fn test_import_contracts_with_shared_prefix() {
+    // Both contracts start with the same 32-byte Solidity preamble
+    let prefix = "6080604052348015600f57600080fd5b506004361060285760003560e01c8063";
+    let dump = format!(
+        concat!(
+            r#"{{"balance":"0","nonce":1,"code":"0x{0}a1","address":"0x00000000000000000000000000000000000000a1"}}"#, "\n",
+            r#"{{"balance":"0","nonce":1,"code":"0x{0}a2","address":"0x00000000000000000000000000000000000000a2"}}"#, "\n",
+        ),
+        prefix
+    );
+
+    let mut state = State::new();
+    import_geth_dump(dump.as_bytes(), &mut state).unwrap();
+
+    let first = state.get_code(&Address::from_low_u64_be(0xa1)).unwrap();
+    let second = state.get_code(&Address::from_low_u64_be(0xa2)).unwrap();
+    assert_eq!(first.last(), Some(&0xa1));
+    assert_eq!(second.last(), Some(&0xa2));
+}
+
+#[test]
+fn test_import_replaces_existing_account() {
+    let address = Address::from_low_u64_be(1);
+    let mut state = State::new();
+    state.set_code(address, vec![0x60, 0x01]);
+    state.store_storage(&address, Word::from(9), Word::from(7));
+
+    let dump = r#"{"balance":"5","nonce":0,"storage":{"0x01":"0x02"},"address":"0x0000000000000000000000000000000000000001"}"#;
+    import_geth_dump(dump.as_bytes(), &mut state).unwrap();
+
+    // Stale code and storage are dropped
+    assert_eq!(state.get_balance(&address), Wei::from(5));
+    assert!(state.get_code(&address).is_none());
+    assert_eq!(state.load_storage(&address, &Word::from(9)), Word::zero());
+    assert_eq!(state.load_storage(&address, &Word::from(1)), Word::from(2));
+}
+
+#[test]
+fn test_import_counts_missing_code_and_storage() {
+    // Exported with --nocode --nostorage: hashes are present but contents are not
+    let dump = r#"{"balance":"0","nonce":1,"root":"0x1111111111111111111111111111111111111111111111111111111111111111","codeHash":"0x2222222222222222222222222222222222222222222222222222222222222222","address":"0x0000000000000000000000000000000000000001"}"#;
+
+    let mut state = State::new();
+    let progress = import_geth_dump(dump.as_bytes(), &mut state).unwrap();
+
+    assert_eq!(progress.accounts, 1);
+    assert_eq!(progress.missing_code, 1);
+    assert_eq!(progress.missing_storage, 1);
+}
+
+#[test]
+fn test_import_rejects_non_streaming_dump() {
+    let mut state = State::new();
+
+    // Plain `geth dump` wraps all accounts in a single object
+    let dump = r#"{"root":"0x0101010101010101010101010101010101010101010101010101010101010101","accounts":{"0x0000000000000000000000000000000000000001":{"balance":"1","nonce":0}}}"#;
+    assert!(matches!(import_geth_dump(dump.as_bytes(), &mut state), Err(Error::InvalidSnapshot(_))));
+    assert!(!state.account_exists(&Address::from_low_u64_be(1)));
+
+    // A header is only allowed on the first line
+    let dump = format!("{}\n{}\n", r#"{"balance":"1","nonce":0,"address":"0x0000000000000000000000000000000000000001"}"#, r#"{"root":"0x00"}"#);
+    assert!(matches!(import_geth_dump(dump.as_bytes(), &mut state), Err(Error::InvalidSnapshot(_))));
+
+    // Records that are neither a header nor an account
+    assert!(matches!(import_geth_dump(r#"{"nonce":1}"#.as_bytes(), &mut state), Err(Error::InvalidSnapshot(_))));
+}
@@ -0,0 +1,189 @@
+//! Unit tests for state diffs and human-readable state change reports
+
+use tinyevm::state::diff::StateDiff;
+use tinyevm::state::report::{mapping_slot, StateReporter, StorageLayout, VariableKind};
+use tinyevm::state::State;
+use tinyevm::types::*;
+
+fn holder_key(address: &Address) -> Word {
+    Word::from_big_endian(address.as_bytes())
+}
+
+#[test]
+fn test_state_diff() {
+    let mut state = State::new();
+    let address = Address::from([1u8; 20]);
+    state.add_balance(&address, Wei::from(1000));
+    state.store_storage(&address, Word::from(1), Word::from(100));
+    state.store_storage(&address, Word::from(2), Word::from(7));
+
+    let snapshot = state.snapshot();
+    assert!(state.diff_since(&snapshot).is_empty());
+
+    state.add_balance(&address, Wei::from(500));
+    state.increment_nonce(&address);
+    state.store_storage(&address, Word::from(1), Word::from(200));
+    state.store_storage(&address, Word::from(2), Word::zero());
+    state.store_storage(&address, Word::from(3), Word::from(9));
+
+    let diff = state.diff_since(&snapshot);
+    assert_eq!(diff, StateDiff::between(&snapshot, &state.snapshot()));
+
+    assert_eq!(diff.accounts.len(), 1);
+    assert_eq!(diff.accounts[0].balance, Some((Wei::from(1000), Wei::from(1500))));
+    assert_eq!(diff.accounts[0].nonce, Some((0, 1)));
+    assert!(!diff.accounts[0].code_changed);
+
+    // Slots sorted, deleted slots compare as zero
+    let storage: Vec<_> = diff.storage.iter().map(|c| (c.slot, c.before, c.after)).collect();
+    assert_eq!(storage, vec![
+        (Word::from(1), Word::from(100), Word::from(200)),
+        (Word::from(2), Word::from(7), Word::zero()),
+        (Word::from(3), Word::zero(), Word::from(9)),
+    ]);
+}
+
+#[test]
+fn test_report_decodes_mappings_and_values() {
+    let mut state = State::new();
+    let token = Address::from_low_u64_be(0xaaaa);
+    let holder = Address::from([0xab; 20]);
+
+    state.store_storage(&token, mapping_slot(&holder_key(&holder), &Word::zero()), Word::from(100));
+    let snapshot = state.snapshot();
+
+    state.store_storage(&token, mapping_slot(&holder_key(&holder), &Word::zero()), Word::from(250));
+    state.store_storage(&token, Word::from(2), Word::from(1_000_000));
+    state.store_storage(&token, Word::from(9), Word::from(1));
+
+    let layout = StorageLayout::new()
+        .with_mapping("balanceOf", 0)
+        .with_value("totalSupply", 2);
+    let report = StateReporter::new()
+        .with_contract(token, "USDC", layout)
+        .with_known_address(holder)
+        .report(&state.diff_since(&snapshot));
+
+    let lines: Vec<String> = report.lines.iter().map(|line| line.to_string()).collect();
+    assert_eq!(lines, vec![
+        "USDC.totalSupply 0 → 1000000".to_string(),
+        "USDC.slot[0x9] 0 → 1".to_string(),
+        "USDC.balanceOf(0xabab…abab) 100 → 250".to_string(),
+    ]);
+}
+
+#[test]
+fn test_report_unlabeled_accounts() {
+    let mut state = State::new();
+    let address = Address::from_low_u64_be(0x1234);
+    let snapshot = state.snapshot();
+
+    state.add_balance(&address, Wei::from(5));
+    state.store_storage(&address, Word::from(16), Word::from(1));
+
+    let report = StateReporter::new().report(&state.diff_since(&snapshot));
+    assert_eq!(
+        report.to_string(),
+        "0x0000…1234.balance 0 → 5\n0x0000…1234.slot[0x10] 0 → 1\n"
+    );
+}
+
+#[test]
+fn test_storage_layout_from_solc_json() {
+    let json = r#"{
+        "storage": [
+            {"astId": 3, "contract": "Token", "label": "_balances", "offset": 0, "slot": "0", "type": "t_mapping(t_address,t_uint256)"},
+            {"astId": 5, "contract": "Token", "label": "_totalSupply", "offset": 0, "slot": "2", "type": "t_uint256"}
+        ],
+        "types": {}
+    }"#;
+
+    let layout = StorageLayout::from_solc_json(json)
+        .unwrap()
+        .rename("_balances", "balanceOf");
+
+    assert_eq!(layout.variables.len(), 2);
+    assert_eq!(layout.variables[0].name, "balanceOf");
+    assert_eq!(layout.variables[0].kind, VariableKind::Mapping);
+    assert_eq!(layout.variables[1].name, "_totalSupply");
+    assert_eq!(layout.variables[1].slot, Word::from(2));
+    assert_eq!(layout.variables[1].kind, VariableKind::Value);
+
+    assert!(StorageLayout::from_solc_json(r#"{"storage": [{"label": "x", "slot": "zz", "type": "t_uint256"}]}"#).is_err());
+}
+
+#[test]
+fn test_packed_slot_from_solc_json() {
+    // `owner` (20 bytes) and `paused` (1 byte) share slot 0
+    let json = r#"{
+        "storage": [
+            {"astId": 3, "contract": "Token", "label": "owner", "offset": 0, "slot": "0", "type": "t_address"},
+            {"astId": 5, "contract": "Token", "label": "paused", "offset": 20, "slot": "0", "type": "t_bool"}
+        ],
+        "types": {
+            "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+            "t_bool": {"encoding": "inplace", "label": "bool", "numberOfBytes": "1"}
+        }
+    }"#;
+
+    let layout = StorageLayout::from_solc_json(json).unwrap();
+    assert_eq!((layout.variables[0].offset, layout.variables[0].size), (0, 20));
+    assert_eq!((layout.variables[1].offset, layout.variables[1].size), (20, 1));
+
+    let mut state = State::new();
+    let token = Address::from_low_u64_be(0xaaaa);
+    let owner = Word::from(0x1234);
+    state.store_storage(&token, Word::zero(), owner);
+    let snapshot = state.snapshot();
+
+    // Set `paused` without touching `owner`
+    state.store_storage(&token, Word::zero(), owner | (Word::one() << 160));
+
+    let report = StateReporter::new()
+        .with_contract(token, "Token", layout)
+        .report(&state.diff_since(&snapshot));
+    assert_eq!(report.to_string(), "Token.paused 0 → 1\n");
+}
+
+#[test]
+fn test_report_code_changes() {
+    let mut state = State::new();
+    let token = Address::from_low_u64_be(0xaaaa);
+    let snapshot = state.snapshot();
+
+    state.set_code(token, vec![0x60, 0x01]);
+
+    let report = StateReporter::new()
+        .with_contract(token, "USDC", StorageLayout::new())
+        .report(&state.diff_since(&snapshot));
+    assert_eq!(report.to_string(), "USDC.code changed\n");
+}
+
+#[test]
+fn test_report_mapping_key_formatting() {
+    let mut state = State::new();
+    let token = Address::from_low_u64_be(0xaaaa);
+    let precompile = Address::from_low_u64_be(0x1234);
+    let token_id = Word::from(7);
+    let snapshot = state.snapshot();
+
+    // Numerically small address keys are still shown as addresses
+    state.store_storage(&token, mapping_slot(&holder_key(&precompile), &Word::zero()), Word::from(1));
+    state.store_storage(&token, mapping_slot(&token_id, &Word::one()), Word::from(2));
+
+    let layout = StorageLayout::new()
+        .with_mapping("balanceOf", 0)
+        .with_mapping("ownerOf", 1);
+    let report = StateReporter::new()
+        .with_contract(token, "NFT", layout)
+        .with_known_address(precompile)
+        .with_known_key(token_id)
+        .report(&state.diff_since(&snapshot));
+
+    let mut lines: Vec<String> = report.lines.iter().map(|line| line.to_string()).collect();
+    lines.sort();
+    assert_eq!(lines, vec![
+        "NFT.balanceOf(0x0000…1234) 0 → 1".to_string(),
+        "NFT.ownerOf(7) 0 → 2".to_string(),
+    ]);
+}